@@ -0,0 +1,69 @@
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Per-feed authentication: HTTP basic auth, arbitrary extra headers (a
+/// `Cookie:` or `X-Api-Key:` value for private trackers), and an optional
+/// override of the User-Agent sent with the request.
+#[derive(Debug, Deserialize)]
+pub struct AuthConfig {
+    pub basic: Option<BasicAuthConfig>,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    pub user_agent: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BasicAuthConfig {
+    pub username: String,
+    pub password: String,
+}
+
+const DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// Builds a `reqwest::Client` carrying a feed's default headers and request
+/// timeout, mirroring how `transmission_rpc::TransClient` injects its own
+/// default User-Agent.
+pub fn build_client(auth: Option<&AuthConfig>, timeout: Duration) -> Result<reqwest::Client> {
+    let mut headers = HeaderMap::new();
+    let mut user_agent = DEFAULT_USER_AGENT.to_string();
+
+    if let Some(auth) = auth {
+        if let Some(ua) = &auth.user_agent {
+            user_agent = ua.clone();
+        }
+        for (key, value) in &auth.headers {
+            let name = HeaderName::from_bytes(key.as_bytes())
+                .with_context(|| format!("Invalid header name: {}", key))?;
+            let value = HeaderValue::from_str(value)
+                .with_context(|| format!("Invalid header value for '{}'", key))?;
+            headers.insert(name, value);
+        }
+    }
+
+    reqwest::Client::builder()
+        .default_headers(headers)
+        .user_agent(user_agent)
+        .timeout(timeout)
+        .build()
+        .context("Failed to build HTTP client")
+}
+
+/// Fetches `url` with `client`, applying HTTP basic auth from `auth` if set.
+pub async fn fetch(client: &reqwest::Client, url: &str, auth: Option<&AuthConfig>) -> Result<String> {
+    let mut request = client.get(url);
+    if let Some(basic) = auth.and_then(|a| a.basic.as_ref()) {
+        request = request.basic_auth(&basic.username, Some(&basic.password));
+    }
+
+    let response = request
+        .send()
+        .await
+        .with_context(|| format!("Failed to download RSS feed: {}", url))?;
+    response
+        .text()
+        .await
+        .with_context(|| format!("Failed to get RSS content: {}", url))
+}
@@ -0,0 +1,291 @@
+use anyhow::{Context, Result};
+use quick_xml::events::Event;
+use serde::Deserialize;
+
+/// How a feed's raw XML should be turned into [`Item`]s.
+///
+/// `Xpath` keeps the legacy tag-path/attribute extraction for feeds that
+/// don't validate as RSS or Atom; `Rss` and `Atom` parse with the standard
+/// crates and know how to pull the `.torrent` link out of an `<enclosure>`.
+#[derive(Debug, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ParserKind {
+    #[default]
+    Xpath,
+    Rss,
+    Atom,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ParserConfig {
+    #[serde(default)]
+    pub kind: ParserKind,
+    /// Comma-separated tag path to match. Only used when `kind` is `xpath`.
+    pub path: Option<String>,
+    /// Attribute to pull the torrent URL from. Only used when `kind` is `xpath`.
+    pub property: Option<String>,
+}
+
+/// A single feed entry, normalized across the xpath/RSS/Atom parsers.
+#[derive(Debug, Clone)]
+pub struct Item {
+    pub title: String,
+    /// The torrent (or magnet) URL to hand to Transmission.
+    pub link: String,
+    /// Stable identifier used for dedup; falls back to `link` when the feed
+    /// has no GUID/id of its own.
+    pub guid: String,
+    pub pub_date: Option<String>,
+    /// Size of the linked torrent/enclosure in bytes, when the feed reports one.
+    pub size: Option<u64>,
+}
+
+/// Parses `xml_content` according to `parser_config.kind`.
+pub fn parse(xml_content: &str, parser_config: &ParserConfig) -> Result<Vec<Item>> {
+    match parser_config.kind {
+        ParserKind::Xpath => parse_xpath(xml_content, parser_config),
+        ParserKind::Rss => parse_rss(xml_content),
+        ParserKind::Atom => parse_atom(xml_content),
+    }
+}
+
+fn parse_rss(xml_content: &str) -> Result<Vec<Item>> {
+    let channel = rss::Channel::read_from(xml_content.as_bytes())
+        .context("Failed to parse RSS feed")?;
+
+    let items = channel
+        .items()
+        .iter()
+        .filter_map(|item| {
+            let enclosure = item.enclosure();
+            let link = enclosure
+                .map(|e| e.url().to_string())
+                .or_else(|| item.link().map(|l| l.to_string()))?;
+            let guid = item
+                .guid()
+                .map(|g| g.value().to_string())
+                .unwrap_or_else(|| link.clone());
+            Some(Item {
+                title: item.title().unwrap_or(&link).to_string(),
+                link,
+                guid,
+                pub_date: item.pub_date().map(|d| d.to_string()),
+                size: enclosure.and_then(|e| e.length().parse().ok()),
+            })
+        })
+        .collect();
+
+    Ok(items)
+}
+
+fn parse_atom(xml_content: &str) -> Result<Vec<Item>> {
+    let feed =
+        atom_syndication::Feed::read_from(xml_content.as_bytes()).context("Failed to parse Atom feed")?;
+
+    let items = feed
+        .entries()
+        .iter()
+        .filter_map(|entry| {
+            // Prefer the `rel="enclosure"` link (the .torrent) over whichever
+            // link happens to come first, which is often the `rel="alternate"`
+            // HTML page - mirrors the RSS parser's enclosure-first behavior.
+            let link_elem = entry
+                .links()
+                .iter()
+                .find(|l| l.rel() == "enclosure")
+                .or_else(|| entry.links().first())?;
+            let link = link_elem.href().to_string();
+            let size = link_elem.length().and_then(|len| len.parse().ok());
+            let guid = if entry.id().is_empty() {
+                link.clone()
+            } else {
+                entry.id().to_string()
+            };
+            Some(Item {
+                // atom_syndication's `Entry::title()` returns a `&Text` text-construct
+                // (this crate pins atom_syndication 0.12), not a plain `&str` -
+                // pull the literal text out of it rather than relying on `Display`.
+                title: entry.title().value.clone(),
+                link,
+                guid,
+                pub_date: entry.published().map(|d| d.to_rfc3339()),
+                size,
+            })
+        })
+        .collect();
+
+    Ok(items)
+}
+
+fn parse_xpath(xml_content: &str, parser_config: &ParserConfig) -> Result<Vec<Item>> {
+    let path = parser_config
+        .path
+        .as_deref()
+        .context("xpath parser requires a 'path'")?;
+    let property = parser_config
+        .property
+        .as_deref()
+        .context("xpath parser requires a 'property'")?;
+
+    let mut items = Vec::new();
+    let mut reader = quick_xml::Reader::from_str(xml_content);
+    reader.config_mut().trim_text(true);
+
+    let path_parts: Vec<&str> = path.split(',').collect();
+    let mut current_path = Vec::new();
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf).unwrap() {
+            Event::Start(e) => {
+                let name = std::str::from_utf8(e.name().into_inner())?;
+                current_path.push(name.to_string());
+            }
+            Event::End(_) => {
+                current_path.pop();
+            }
+            Event::Empty(e) => {
+                let name = std::str::from_utf8(e.name().into_inner())?;
+                let mut check_path = current_path.clone();
+                check_path.push(name.to_string());
+                if check_path == path_parts {
+                    let attributes = e.attributes();
+                    for attr in attributes {
+                        if let Ok(attr) = attr {
+                            if let Ok(key) = std::str::from_utf8(attr.key.into_inner()) {
+                                if key == property {
+                                    if let Ok(value) = std::str::from_utf8(&attr.value.into_owned())
+                                    {
+                                        items.push(Item {
+                                            title: value.to_string(),
+                                            link: value.to_string(),
+                                            guid: value.to_string(),
+                                            pub_date: None,
+                                            size: None,
+                                        });
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+        buf.clear();
+    }
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rss_prefers_enclosure_over_link() {
+        let xml = r#"<?xml version="1.0"?>
+        <rss version="2.0">
+        <channel>
+        <title>Test</title>
+        <item>
+        <title>Item One</title>
+        <guid>guid-1</guid>
+        <link>http://example.com/page</link>
+        <enclosure url="http://example.com/one.torrent" length="12345" type="application/x-bittorrent"/>
+        <pubDate>Mon, 01 Jan 2024 00:00:00 GMT</pubDate>
+        </item>
+        </channel>
+        </rss>"#;
+
+        let items = parse_rss(xml).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].title, "Item One");
+        assert_eq!(items[0].link, "http://example.com/one.torrent");
+        assert_eq!(items[0].guid, "guid-1");
+        assert_eq!(items[0].size, Some(12345));
+    }
+
+    #[test]
+    fn rss_falls_back_to_link_without_enclosure() {
+        let xml = r#"<?xml version="1.0"?>
+        <rss version="2.0">
+        <channel>
+        <title>Test</title>
+        <item>
+        <title>Item One</title>
+        <link>http://example.com/page</link>
+        </item>
+        </channel>
+        </rss>"#;
+
+        let items = parse_rss(xml).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].link, "http://example.com/page");
+        assert_eq!(items[0].size, None);
+    }
+
+    #[test]
+    fn atom_prefers_enclosure_over_alternate_link() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+        <feed xmlns="http://www.w3.org/2005/Atom">
+        <title>Test Feed</title>
+        <id>urn:test</id>
+        <updated>2024-01-01T00:00:00Z</updated>
+        <entry>
+        <title>Entry One</title>
+        <id>entry-1</id>
+        <updated>2024-01-01T00:00:00Z</updated>
+        <link rel="alternate" href="http://example.com/page"/>
+        <link rel="enclosure" href="http://example.com/one.torrent" length="6789"/>
+        </entry>
+        </feed>"#;
+
+        let items = parse_atom(xml).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].title, "Entry One");
+        assert_eq!(items[0].link, "http://example.com/one.torrent");
+        assert_eq!(items[0].guid, "entry-1");
+        assert_eq!(items[0].size, Some(6789));
+    }
+
+    #[test]
+    fn atom_falls_back_to_first_link_without_enclosure() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+        <feed xmlns="http://www.w3.org/2005/Atom">
+        <title>Test Feed</title>
+        <id>urn:test</id>
+        <updated>2024-01-01T00:00:00Z</updated>
+        <entry>
+        <title>Entry One</title>
+        <id>entry-1</id>
+        <updated>2024-01-01T00:00:00Z</updated>
+        <link rel="alternate" href="http://example.com/page"/>
+        </entry>
+        </feed>"#;
+
+        let items = parse_atom(xml).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].link, "http://example.com/page");
+    }
+
+    #[test]
+    fn xpath_extracts_configured_attribute() {
+        let xml = r#"<?xml version="1.0"?>
+        <rss>
+        <channel>
+        <item>
+        <enclosure url="http://example.com/one.torrent"/>
+        </item>
+        </channel>
+        </rss>"#;
+        let parser_config = ParserConfig {
+            kind: ParserKind::Xpath,
+            path: Some("rss,channel,item,enclosure".to_string()),
+            property: Some("url".to_string()),
+        };
+
+        let items = parse_xpath(xml, &parser_config).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].link, "http://example.com/one.torrent");
+    }
+}
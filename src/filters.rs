@@ -0,0 +1,175 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::feed::Item;
+
+/// Raw, deserialized filter settings for a feed.
+#[derive(Debug, Deserialize)]
+pub struct FilterConfig {
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    pub min_size: Option<u64>,
+    pub max_size: Option<u64>,
+}
+
+/// Compiled, ready-to-match version of a [`FilterConfig`].
+pub struct Filters {
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+}
+
+impl Filters {
+    pub fn compile(config: &FilterConfig) -> Result<Self> {
+        let include = config
+            .include
+            .iter()
+            .map(|p| Regex::new(p).with_context(|| format!("Invalid include pattern: {}", p)))
+            .collect::<Result<_>>()?;
+        let exclude = config
+            .exclude
+            .iter()
+            .map(|p| Regex::new(p).with_context(|| format!("Invalid exclude pattern: {}", p)))
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            include,
+            exclude,
+            min_size: config.min_size,
+            max_size: config.max_size,
+        })
+    }
+
+    /// Whether a `min_size`/`max_size` bound is configured, so callers can
+    /// warn when an item has no reported size to apply it to.
+    pub fn has_size_bounds(&self) -> bool {
+        self.min_size.is_some() || self.max_size.is_some()
+    }
+
+    /// Returns `Ok(())` if `item` passes every rule, otherwise the reason it was skipped.
+    pub fn check(&self, item: &Item) -> Result<(), String> {
+        if !self.include.is_empty() && !self.include.iter().any(|re| re.is_match(&item.title)) {
+            return Err(format!(
+                "title '{}' matched no include pattern",
+                item.title
+            ));
+        }
+
+        if let Some(re) = self.exclude.iter().find(|re| re.is_match(&item.title)) {
+            return Err(format!(
+                "title '{}' matched exclude pattern '{}'",
+                item.title,
+                re.as_str()
+            ));
+        }
+
+        if let Some(size) = item.size {
+            if let Some(min_size) = self.min_size {
+                if size < min_size {
+                    return Err(format!("size {} is below min_size {}", size, min_size));
+                }
+            }
+            if let Some(max_size) = self.max_size {
+                if size > max_size {
+                    return Err(format!("size {} is above max_size {}", size, max_size));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(title: &str, size: Option<u64>) -> Item {
+        Item {
+            title: title.to_string(),
+            link: "http://example.com/item.torrent".to_string(),
+            guid: "guid".to_string(),
+            pub_date: None,
+            size,
+        }
+    }
+
+    fn filters(config: FilterConfig) -> Filters {
+        Filters::compile(&config).unwrap()
+    }
+
+    #[test]
+    fn no_rules_passes_everything() {
+        let f = filters(FilterConfig {
+            include: vec![],
+            exclude: vec![],
+            min_size: None,
+            max_size: None,
+        });
+        assert!(f.check(&item("Anything", None)).is_ok());
+    }
+
+    #[test]
+    fn include_pattern_must_match() {
+        let f = filters(FilterConfig {
+            include: vec!["^Wanted".to_string()],
+            exclude: vec![],
+            min_size: None,
+            max_size: None,
+        });
+        assert!(f.check(&item("Wanted Show S01E01", None)).is_ok());
+        assert!(f.check(&item("Unwanted Show S01E01", None)).is_err());
+    }
+
+    #[test]
+    fn exclude_pattern_rejects_match() {
+        let f = filters(FilterConfig {
+            include: vec![],
+            exclude: vec!["SAMPLE".to_string()],
+            min_size: None,
+            max_size: None,
+        });
+        assert!(f.check(&item("Real Show S01E01", None)).is_ok());
+        assert!(f.check(&item("Show SAMPLE", None)).is_err());
+    }
+
+    #[test]
+    fn min_size_rejects_smaller_items() {
+        let f = filters(FilterConfig {
+            include: vec![],
+            exclude: vec![],
+            min_size: Some(1000),
+            max_size: None,
+        });
+        assert!(f.check(&item("Small", Some(500))).is_err());
+        assert!(f.check(&item("Big", Some(1500))).is_ok());
+    }
+
+    #[test]
+    fn max_size_rejects_larger_items() {
+        let f = filters(FilterConfig {
+            include: vec![],
+            exclude: vec![],
+            min_size: None,
+            max_size: Some(1000),
+        });
+        assert!(f.check(&item("Big", Some(1500))).is_err());
+        assert!(f.check(&item("Small", Some(500))).is_ok());
+    }
+
+    #[test]
+    fn size_bounds_are_skipped_when_item_has_no_size() {
+        let f = filters(FilterConfig {
+            include: vec![],
+            exclude: vec![],
+            min_size: Some(1000),
+            max_size: Some(2000),
+        });
+        assert!(f.check(&item("Unknown size", None)).is_ok());
+        assert!(f.has_size_bounds());
+    }
+}
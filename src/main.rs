@@ -1,18 +1,43 @@
+mod client;
+mod feed;
+mod filters;
+mod status;
+mod store;
+
 use anyhow::{Context, Result};
 use clap::Parser;
-use quick_xml::events::Event;
+use client::AuthConfig;
+use feed::ParserConfig;
+use filters::{FilterConfig, Filters};
 use serde::Deserialize;
+use status::{PollStats, StatusServerConfig, Tracker};
 use std::collections::HashMap;
 use std::fs;
+use std::time::Duration;
+use store::SeenStore;
+use tokio::time::Instant;
 use transmission_rpc::{TransClient, types::BasicAuth, types::TorrentAddArgs};
 use url::Url;
 
+/// Default polling interval, in seconds, for feeds that don't set `refresh_time`.
+const DEFAULT_REFRESH_TIME: u64 = 300;
+/// Default per-request timeout, in seconds, for feeds that don't set `timeout`.
+const DEFAULT_REQUEST_TIMEOUT: u64 = 30;
+
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
     /// Path to the app configuration file
     #[arg(short, long, default_value = "config/app.yaml")]
     config: String,
+
+    /// Poll every feed once and exit instead of running as a daemon
+    #[arg(long)]
+    once: bool,
+
+    /// Print which items would be added/skipped and why, without adding anything
+    #[arg(long)]
+    dry_run: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -21,6 +46,14 @@ struct Config {
     transmission_rpc: TransmissionConfig,
     rss: Vec<RssConfig>,
     parser: HashMap<String, ParserConfig>,
+    /// Default polling interval in seconds, overridable per-feed via `RssConfig::refresh_time`.
+    refresh_time: Option<u64>,
+    /// Path to the persistent dedup store. Defaults to `seen.json`.
+    seen_store: Option<String>,
+    /// Default per-feed request timeout in seconds, overridable via `RssConfig::timeout`.
+    request_timeout: Option<u64>,
+    /// Serves a JSON status document when present; omitted entirely for single-shot use.
+    status_server: Option<StatusServerConfig>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -36,12 +69,22 @@ struct TransmissionConfig {
 struct RssConfig {
     url: String,
     parser: String,
-}
-
-#[derive(Debug, Deserialize)]
-struct ParserConfig {
-    path: String,
-    property: String,
+    /// Per-feed polling interval in seconds, overriding `Config::refresh_time`.
+    refresh_time: Option<u64>,
+    /// Include/exclude title patterns and size bounds applied before adding.
+    filters: Option<FilterConfig>,
+    /// Directory Transmission should save this feed's torrents to.
+    download_dir: Option<String>,
+    /// Labels to tag added torrents with.
+    labels: Option<Vec<String>>,
+    /// Add torrents in a paused state instead of starting them immediately.
+    paused: Option<bool>,
+    /// Transmission bandwidth priority: -1 low, 0 normal, 1 high.
+    bandwidth_priority: Option<i64>,
+    /// Basic auth, extra headers, and User-Agent override for private trackers.
+    auth: Option<AuthConfig>,
+    /// Per-feed request timeout in seconds, overriding `Config::request_timeout`.
+    timeout: Option<u64>,
 }
 
 #[tokio::main]
@@ -67,83 +110,167 @@ async fn main() -> Result<()> {
         },
     );
 
-    // Process each RSS feed
-    for rss_config in config.rss {
-        // Get parser config for this RSS feed
-        let parser_config = config.parser.get(&rss_config.parser).with_context(|| {
-            format!("Parser '{}' not found in configuration", rss_config.parser)
-        })?;
+    let seen_store_path = config.seen_store.clone().unwrap_or_else(|| "seen.json".to_string());
+    let mut seen = SeenStore::load(&seen_store_path)?;
 
-        // Download RSS feed
-        let response = reqwest::get(&rss_config.url)
-            .await
-            .with_context(|| format!("Failed to download RSS feed: {}", rss_config.url))?;
-        let xml_content = response
-            .text()
+    // Compile each feed's filter patterns once up front rather than per poll.
+    let filters: Vec<Option<Filters>> = config
+        .rss
+        .iter()
+        .map(|rss_config| rss_config.filters.as_ref().map(Filters::compile).transpose())
+        .collect::<Result<_>>()?;
+
+    // Build one HTTP client per feed carrying its auth headers and timeout.
+    let http_clients: Vec<reqwest::Client> = config
+        .rss
+        .iter()
+        .map(|rss_config| {
+            let timeout = Duration::from_secs(
+                rss_config
+                    .timeout
+                    .or(config.request_timeout)
+                    .unwrap_or(DEFAULT_REQUEST_TIMEOUT),
+            );
+            client::build_client(rss_config.auth.as_ref(), timeout)
+        })
+        .collect::<Result<_>>()?;
+
+    let status = Tracker::shared();
+    if let Some(status_server) = &config.status_server {
+        if let Err(e) = status::spawn(status_server, status.clone()) {
+            eprintln!("Error starting status server: {:#}", e);
+        }
+    }
+
+    // Each feed polls on its own cadence, so track when every feed is next due
+    // rather than sleeping for a single shared interval.
+    let now = Instant::now();
+    let mut next_due: Vec<Instant> = config.rss.iter().map(|_| now).collect();
+
+    loop {
+        let now = Instant::now();
+        for (i, rss_config) in config.rss.iter().enumerate() {
+            if next_due[i] > now {
+                continue;
+            }
+
+            match poll_feed(
+                &mut client,
+                &http_clients[i],
+                rss_config,
+                &config.parser,
+                &mut seen,
+                filters[i].as_ref(),
+                args.dry_run,
+            )
             .await
-            .with_context(|| format!("Failed to get RSS content: {}", rss_config.url))?;
-
-        // Parse XML and extract torrent URLs
-        let urls = parse_xml(&xml_content, parser_config)?;
-
-        // Add torrents to transmission
-        for url in urls {
-            println!("Adding torrent: {}", url);
-            let args = TorrentAddArgs {
-                filename: Some(url.clone()),
-                ..Default::default()
-            };
-            if let Err(e) = client.torrent_add(args).await {
-                eprintln!("Failed to add torrent {}: {}", url, e);
+            {
+                Ok(stats) => status.record_success(&rss_config.url, stats),
+                Err(e) => {
+                    eprintln!("Error polling feed {}: {:#}", rss_config.url, e);
+                    status.record_error(&rss_config.url, e.to_string());
+                }
             }
+
+            let refresh = rss_config
+                .refresh_time
+                .or(config.refresh_time)
+                .unwrap_or(DEFAULT_REFRESH_TIME);
+            next_due[i] = Instant::now() + Duration::from_secs(refresh);
+        }
+
+        seen.flush()?;
+
+        if args.once {
+            break;
         }
+
+        let sleep_until = next_due
+            .iter()
+            .copied()
+            .min()
+            .unwrap_or_else(|| Instant::now() + Duration::from_secs(DEFAULT_REFRESH_TIME));
+        tokio::time::sleep_until(sleep_until).await;
     }
 
     Ok(())
 }
 
-fn parse_xml(xml_content: &str, parser_config: &ParserConfig) -> Result<Vec<String>> {
-    let mut urls = Vec::new();
-    let mut reader = quick_xml::Reader::from_str(xml_content);
-    reader.config_mut().trim_text(true);
+/// Downloads, parses, and adds new torrents for a single feed, recording each
+/// added item in `seen` so future polls don't re-add it.
+async fn poll_feed(
+    client: &mut TransClient,
+    http_client: &reqwest::Client,
+    rss_config: &RssConfig,
+    parsers: &HashMap<String, ParserConfig>,
+    seen: &mut SeenStore,
+    filters: Option<&Filters>,
+    dry_run: bool,
+) -> Result<PollStats> {
+    // Get parser config for this RSS feed
+    let parser_config = parsers.get(&rss_config.parser).with_context(|| {
+        format!("Parser '{}' not found in configuration", rss_config.parser)
+    })?;
 
-    // Parse the path configuration
-    let path_parts: Vec<&str> = parser_config.path.split(',').collect();
-    let mut current_path = Vec::new();
-    let mut buf = Vec::new();
-    loop {
-        match reader.read_event_into(&mut buf).unwrap() {
-            Event::Start(e) => {
-                let name = std::str::from_utf8(e.name().into_inner())?;
-                current_path.push(name.to_string());
+    // Download RSS feed, applying this feed's auth headers/timeout
+    let xml_content =
+        client::fetch(http_client, &rss_config.url, rss_config.auth.as_ref()).await?;
+
+    // Parse the feed and extract torrent items
+    let items = feed::parse(&xml_content, parser_config)?;
+    let items_seen = items.len() as u64;
+    let mut items_added = 0;
+
+    // Add torrents to transmission, skipping ones already seen or filtered out
+    for item in items {
+        if seen.contains(&rss_config.url, &item.guid) {
+            if dry_run {
+                println!("Would skip: {} (already seen)", item.title);
             }
-            Event::End(_) => {
-                current_path.pop();
+            continue;
+        }
+
+        if let Some(filters) = filters {
+            if dry_run && item.size.is_none() && filters.has_size_bounds() {
+                println!(
+                    "Note: {} reports no size, size filter not applied",
+                    item.title
+                );
             }
-            Event::Empty(e) => {
-                let name = std::str::from_utf8(e.name().into_inner())?;
-                let mut check_path = current_path.clone();
-                check_path.push(name.to_string());
-                if check_path == path_parts {
-                    let attributes = e.attributes();
-                    for attr in attributes {
-                        if let Ok(attr) = attr {
-                            if let Ok(key) = std::str::from_utf8(attr.key.into_inner()) {
-                                if key == parser_config.property {
-                                    if let Ok(value) = std::str::from_utf8(&attr.value.into_owned())
-                                    {
-                                        urls.push(value.to_string());
-                                    }
-                                }
-                            }
-                        }
-                    }
+
+            if let Err(reason) = filters.check(&item) {
+                if dry_run {
+                    println!("Would skip: {} ({})", item.title, reason);
                 }
+                continue;
             }
-            Event::Eof => break,
-            _ => (),
         }
-        buf.clear();
+
+        if dry_run {
+            println!("Would add: {} ({})", item.title, item.link);
+            continue;
+        }
+
+        println!("Adding torrent: {} ({})", item.title, item.link);
+        let add_args = TorrentAddArgs {
+            filename: Some(item.link.clone()),
+            download_dir: rss_config.download_dir.clone(),
+            labels: rss_config.labels.clone(),
+            paused: rss_config.paused,
+            bandwidth_priority: rss_config.bandwidth_priority,
+            ..Default::default()
+        };
+        if let Err(e) = client.torrent_add(add_args).await {
+            eprintln!("Failed to add torrent {}: {}", item.link, e);
+            continue;
+        }
+
+        seen.mark_seen(&rss_config.url, &item.guid);
+        items_added += 1;
     }
-    Ok(urls)
+
+    Ok(PollStats {
+        items_seen,
+        items_added,
+    })
 }
@@ -0,0 +1,89 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Host/port to serve the JSON status document on. Presence of this block in
+/// config is what gates the server on; single-shot CLI users who omit it pay
+/// nothing for it.
+#[derive(Debug, Deserialize)]
+pub struct StatusServerConfig {
+    pub host: String,
+    pub port: u16,
+}
+
+/// Per-feed counters recorded after each poll.
+#[derive(Debug, Default, Serialize, Clone)]
+pub struct FeedStatus {
+    pub last_poll: Option<String>,
+    pub items_seen: u64,
+    pub items_added: u64,
+    pub last_error: Option<String>,
+}
+
+/// What a successful poll of a feed found, reported back up to [`Tracker`].
+pub struct PollStats {
+    pub items_seen: u64,
+    pub items_added: u64,
+}
+
+/// Shared, thread-safe home for the status the HTTP endpoint serves, updated
+/// by the polling loop as it runs.
+#[derive(Debug, Default)]
+pub struct Tracker {
+    feeds: Mutex<HashMap<String, FeedStatus>>,
+}
+
+pub type SharedTracker = Arc<Tracker>;
+
+impl Tracker {
+    pub fn shared() -> SharedTracker {
+        Arc::new(Self::default())
+    }
+
+    pub fn record_success(&self, feed_url: &str, stats: PollStats) {
+        self.update(feed_url, |status| {
+            status.items_seen = stats.items_seen;
+            status.items_added = stats.items_added;
+            status.last_error = None;
+        });
+    }
+
+    pub fn record_error(&self, feed_url: &str, error: String) {
+        self.update(feed_url, |status| {
+            status.last_error = Some(error);
+        });
+    }
+
+    fn update(&self, feed_url: &str, f: impl FnOnce(&mut FeedStatus)) {
+        let mut feeds = self.feeds.lock().unwrap();
+        let status = feeds.entry(feed_url.to_string()).or_default();
+        status.last_poll = Some(chrono::Utc::now().to_rfc3339());
+        f(status);
+    }
+
+    fn snapshot(&self) -> HashMap<String, FeedStatus> {
+        self.feeds.lock().unwrap().clone()
+    }
+}
+
+/// Starts the status HTTP server on a background thread. `tiny_http`'s
+/// server is synchronous, so it gets its own OS thread rather than a tokio task.
+pub fn spawn(config: &StatusServerConfig, tracker: SharedTracker) -> Result<()> {
+    let server = tiny_http::Server::http(format!("{}:{}", config.host, config.port))
+        .map_err(|e| anyhow!("Failed to bind status server: {}", e))?;
+
+    std::thread::spawn(move || {
+        for request in server.incoming_requests() {
+            let body = serde_json::to_string(&tracker.snapshot())
+                .unwrap_or_else(|_| "{}".to_string());
+            let header =
+                tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+                    .expect("static header is valid");
+            let response = tiny_http::Response::from_string(body).with_header(header);
+            let _ = request.respond(response);
+        }
+    });
+
+    Ok(())
+}
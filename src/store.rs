@@ -0,0 +1,88 @@
+use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Tracks which feed items have already been handed to Transmission, persisted
+/// to disk so re-running the daemon (or restarting it after a crash) doesn't
+/// re-add torrents it already queued.
+#[derive(Debug, Default)]
+pub struct SeenStore {
+    path: String,
+    seen: HashMap<String, HashSet<String>>,
+}
+
+impl SeenStore {
+    /// Loads the store from `path`, treating a missing file as an empty store.
+    pub fn load(path: &str) -> Result<Self> {
+        let seen = if Path::new(path).exists() {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read seen store: {}", path))?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse seen store: {}", path))?
+        } else {
+            HashMap::new()
+        };
+        Ok(Self {
+            path: path.to_string(),
+            seen,
+        })
+    }
+
+    pub fn contains(&self, feed_url: &str, item_key: &str) -> bool {
+        self.seen
+            .get(feed_url)
+            .is_some_and(|keys| keys.contains(item_key))
+    }
+
+    pub fn mark_seen(&mut self, feed_url: &str, item_key: &str) {
+        self.seen
+            .entry(feed_url.to_string())
+            .or_default()
+            .insert(item_key.to_string());
+    }
+
+    /// Writes the store back to disk. Called after each polling round.
+    pub fn flush(&self) -> Result<()> {
+        let content = serde_json::to_string_pretty(&self.seen)?;
+        fs::write(&self.path, content)
+            .with_context(|| format!("Failed to write seen store: {}", self.path))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("transmission-rss-test-{}-{}.json", std::process::id(), name))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn missing_file_loads_as_empty() {
+        let path = temp_path("missing");
+        let store = SeenStore::load(&path).unwrap();
+        assert!(!store.contains("http://example.com/feed", "guid-1"));
+    }
+
+    #[test]
+    fn save_and_reload_round_trips() {
+        let path = temp_path("roundtrip");
+        let _ = fs::remove_file(&path);
+
+        let mut store = SeenStore::load(&path).unwrap();
+        store.mark_seen("http://example.com/feed", "guid-1");
+        store.flush().unwrap();
+
+        let reloaded = SeenStore::load(&path).unwrap();
+        assert!(reloaded.contains("http://example.com/feed", "guid-1"));
+        assert!(!reloaded.contains("http://example.com/feed", "guid-2"));
+        assert!(!reloaded.contains("http://example.com/other-feed", "guid-1"));
+
+        fs::remove_file(&path).unwrap();
+    }
+}